@@ -0,0 +1,370 @@
+use anyhow::Result;
+use ratatui::prelude::*;
+use ratatui::widgets::{Block, Borders, Paragraph, Wrap};
+use std::sync::{Arc, RwLock};
+use tokio::runtime;
+
+use crate::backend::AppEvent;
+use crate::config::UiConfig;
+use crate::filter::FilterMode;
+use crate::storage::History;
+
+pub struct App {
+    flow_marker: bool,
+    ui: UiConfig,
+    state: Arc<RwLock<State>>,
+    input: String,
+    display: String,
+    shared_state: Arc<RwLock<SlowState>>,
+    state_machine: Arc<dyn StateMachine>,
+    history: Arc<RwLock<History>>,
+    filter: FilterMode,
+}
+
+enum State {
+    Input,
+    Processing,
+    Review,
+}
+
+impl State {
+    fn is_input(&self) -> bool {
+        matches!(self, State::Input)
+    }
+    // fn is_processing(&self) -> bool {
+    //     matches!(self, State::Processing)
+    // }
+    // fn is_review(&self) -> bool {
+    //     matches!(self, State::Review)
+    // }
+}
+
+#[async_trait::async_trait]
+pub trait StateMachine: Send + Sync {
+    fn generate(&self, filter: FilterMode) -> Result<String>;
+    async fn process(&self, input: String, logit: String) -> Result<(f64, String)>;
+}
+
+/// Pick the next word, logging and falling back to an empty prompt rather
+/// than crashing the UI thread on a driver error (e.g. a broken Lua script).
+fn generate_or_log(state_machine: &dyn StateMachine, filter: FilterMode) -> String {
+    state_machine.generate(filter).unwrap_or_else(|err| {
+        eprintln!("Failed to generate next word: {err}");
+        String::new()
+    })
+}
+
+struct SlowState {
+    explanation: String,
+    score: f64,
+}
+
+impl App {
+    pub fn new(state_machine: Arc<dyn StateMachine>, ui: UiConfig, history: History) -> Self {
+        let filter = FilterMode::default();
+
+        App {
+            flow_marker: false,
+            ui,
+            state: Arc::new(RwLock::new(State::Input)),
+            input: String::new(),
+            display: generate_or_log(state_machine.as_ref(), filter),
+            shared_state: Arc::new(RwLock::new(SlowState {
+                explanation: String::new(),
+                score: 0.0,
+            })),
+            state_machine,
+            history: Arc::new(RwLock::new(history)),
+            filter,
+        }
+    }
+
+    /// Route a backend-agnostic input event to the matching state
+    /// transition. Returns `true` if the app should quit.
+    pub fn handle_event(&mut self, event: AppEvent, rt: &runtime::Runtime) -> bool {
+        match event {
+            AppEvent::CtrlC | AppEvent::CtrlD | AppEvent::Esc => return true,
+            AppEvent::CtrlR => self.on_retry(),
+            AppEvent::Delete => self.input.clear(),
+            AppEvent::Char(c) => self.on_key(c),
+            AppEvent::Enter => self.on_review(rt),
+            AppEvent::Tab => self.on_next(),
+            AppEvent::CycleFilter => self.filter = self.filter.next(),
+            AppEvent::Backspace => {
+                if self.state.read().unwrap().is_input() {
+                    self.input.pop();
+                }
+            }
+        }
+
+        false
+    }
+
+    fn on_next(&mut self) {
+        self.input.clear();
+        self.display = generate_or_log(self.state_machine.as_ref(), self.filter);
+        let mut shared_state = self
+            .shared_state
+            .write()
+            .expect("Failed to lock shared state");
+        shared_state.explanation.clear();
+        shared_state.score = 0.0;
+        drop(shared_state);
+        *self.state.write().expect("Failed to lock state") = State::Input;
+
+        if let Err(err) = self.history.read().expect("Failed to lock history").save() {
+            eprintln!("Failed to save history: {err}");
+        }
+    }
+
+    fn on_review(&mut self, rt: &runtime::Runtime) {
+        if !self.state.read().unwrap().is_input() {
+            return;
+        }
+
+        let input = self.input.clone();
+        let logit = self.display.clone();
+        let state_machine = self.state_machine.clone();
+        let shared_state = self.shared_state.clone();
+        let history = self.history.clone();
+
+        *self.state.write().unwrap() = State::Processing;
+
+        let state = self.state.clone();
+
+        rt.spawn(async move {
+            let result = state_machine.process(input.clone(), logit.clone()).await;
+
+            let (score, explanation) = match result {
+                Ok(result) => result,
+                Err(err) => {
+                    eprintln!("Failed to process review: {err}");
+                    let mut state = state.write().unwrap();
+                    if let State::Processing = *state {
+                        *state = State::Input;
+                    }
+                    return;
+                }
+            };
+
+            let mut state = state.write().unwrap();
+            if let State::Processing = *state {
+                let mut shared_state = shared_state.write().unwrap();
+                shared_state.score = score;
+                shared_state.explanation = explanation.clone();
+                *state = State::Review;
+                drop(shared_state);
+
+                let mut history = history.write().unwrap();
+                history.record(logit, input, score, explanation);
+                if let Err(err) = history.save() {
+                    eprintln!("Failed to save history: {err}");
+                }
+            }
+        });
+    }
+
+    fn on_key(&mut self, c: char) {
+        if !self.state.read().unwrap().is_input() {
+            return;
+        }
+        self.input.push(c);
+    }
+
+    fn on_retry(&mut self) {
+        self.input.clear();
+        let mut shared_state = self
+            .shared_state
+            .write()
+            .expect("Failed to lock shared state");
+        shared_state.explanation.clear();
+        shared_state.score = 0.0;
+        drop(shared_state);
+        *self.state.write().expect("Failed to lock state") = State::Input;
+    }
+
+    pub fn ui(&mut self, f: &mut Frame) {
+        let style = Style::new()
+            .bg(Color::Rgb(0, 0, 0))
+            .fg(Color::Rgb(255, 255, 255));
+
+        let input_style = {
+            let state = self.state.read().unwrap();
+            match *state {
+                State::Input => style,
+                State::Processing => Style::default().bg(Color::Rgb(0, 0, 0)).fg(Color::Yellow),
+                State::Review => Style::default().bg(Color::Rgb(0, 0, 0)).fg(Color::Green),
+            }
+        };
+
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Ratio(2, 5),
+                Constraint::Ratio(2, 5),
+                Constraint::Length(1),
+            ])
+            .split(f.area());
+
+        let top_box = Paragraph::new(self.display.as_str())
+            .block(Block::bordered().title(self.ui.top_box_name.as_str()))
+            .style(style)
+            .alignment(Alignment::Center)
+            .wrap(Wrap { trim: true });
+        f.render_widget(top_box, chunks[0]);
+
+        let help_line = {
+            let history = self.history.read().expect("Failed to lock history");
+            format!(
+                "Esc: Quit | Enter: Evaluate | Tab: Next | F2: Mode ({}) | Words: {} | Avg: {:.2}",
+                self.filter.label(),
+                history.words_seen(),
+                history.average_score()
+            )
+        };
+        let last_line = Paragraph::new(help_line)
+            .style(style)
+            .alignment(Alignment::Center);
+        f.render_widget(last_line, chunks[2]);
+
+        let middle_chunks = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([Constraint::Ratio(1, 2), Constraint::Ratio(1, 2)])
+            .split(chunks[1]);
+
+        let input_text = if self.flow_marker {
+            self.flow_marker = false;
+            Line::from(vec![self.input.as_str().into(), "_".into()])
+        } else {
+            self.flow_marker = true;
+            Line::from(self.input.as_str())
+        };
+
+        let input_box = Paragraph::new(input_text)
+            .block(
+                Block::default()
+                    .title(self.ui.left_box_name.as_str())
+                    .borders(Borders::ALL),
+            )
+            .style(input_style)
+            .wrap(Wrap { trim: true });
+        f.render_widget(input_box, middle_chunks[0]);
+
+        let right_chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Ratio(6, 7), Constraint::Ratio(1, 7)])
+            .split(middle_chunks[1]);
+        {
+            let shared_state = self.shared_state.read().unwrap();
+            let right_top_box = Paragraph::new(shared_state.explanation.as_str())
+                .block(
+                    Block::default()
+                        .title(self.ui.right_top_name.as_str())
+                        .borders(Borders::ALL),
+                )
+                .style(style)
+                .wrap(Wrap { trim: true });
+            f.render_widget(right_top_box, right_chunks[0]);
+            let right_bottom_box = Paragraph::new(format!("{:.2}", shared_state.score))
+                .block(
+                    Block::default()
+                        .title(self.ui.right_bottom_name.as_str())
+                        .borders(Borders::ALL),
+                )
+                .style(style)
+                .alignment(Alignment::Center)
+                .wrap(Wrap { trim: true });
+            f.render_widget(right_bottom_box, right_chunks[1]);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::backend::{Backend, MockBackend};
+
+    struct StubStateMachine {
+        word: String,
+    }
+
+    #[async_trait::async_trait]
+    impl StateMachine for StubStateMachine {
+        fn generate(&self, _filter: FilterMode) -> Result<String> {
+            Ok(self.word.clone())
+        }
+
+        async fn process(&self, _input: String, _logit: String) -> Result<(f64, String)> {
+            Ok((1.0, "looks right".to_string()))
+        }
+    }
+
+    fn test_app() -> App {
+        let state_machine: Arc<dyn StateMachine> = Arc::new(StubStateMachine {
+            word: "apple".to_string(),
+        });
+        App::new(state_machine, UiConfig::default(), History::default())
+    }
+
+    fn test_runtime() -> runtime::Runtime {
+        runtime::Builder::new_current_thread()
+            .enable_time()
+            .build()
+            .expect("Failed to build test runtime")
+    }
+
+    #[test]
+    fn esc_and_ctrl_keys_quit() {
+        let mut app = test_app();
+        let rt = test_runtime();
+        assert!(app.handle_event(AppEvent::Esc, &rt));
+        assert!(app.handle_event(AppEvent::CtrlC, &rt));
+        assert!(app.handle_event(AppEvent::CtrlD, &rt));
+    }
+
+    #[test]
+    fn typing_and_backspace_edit_input() {
+        let mut app = test_app();
+        let rt = test_runtime();
+        assert!(!app.handle_event(AppEvent::Char('h'), &rt));
+        assert!(!app.handle_event(AppEvent::Char('i'), &rt));
+        assert_eq!(app.input, "hi");
+
+        app.handle_event(AppEvent::Backspace, &rt);
+        assert_eq!(app.input, "h");
+    }
+
+    #[test]
+    fn cycle_filter_advances_mode() {
+        let mut app = test_app();
+        let rt = test_runtime();
+        assert_eq!(app.filter, FilterMode::All);
+        app.handle_event(AppEvent::CycleFilter, &rt);
+        assert_eq!(app.filter, FilterMode::Due);
+    }
+
+    #[test]
+    fn tab_resets_input_and_draws_next_word() {
+        let mut app = test_app();
+        let rt = test_runtime();
+        app.handle_event(AppEvent::Char('x'), &rt);
+        app.handle_event(AppEvent::Tab, &rt);
+        assert_eq!(app.input, "");
+        assert_eq!(app.display, "apple");
+    }
+
+    #[test]
+    fn mock_backend_renders_the_current_word() {
+        let mut app = test_app();
+        let mut backend = MockBackend::new(40, 10, vec![]);
+        backend.draw(&mut |f| app.ui(f)).unwrap();
+
+        let rendered: String = backend
+            .buffer()
+            .content()
+            .iter()
+            .map(|cell| cell.symbol())
+            .collect();
+        assert!(rendered.contains("apple"));
+    }
+}
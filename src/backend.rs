@@ -0,0 +1,141 @@
+use anyhow::Result;
+use ratatui::Frame;
+use std::time::Duration;
+
+/// A terminal-agnostic input event. `main`'s event loop only ever sees
+/// these, so the `App` state machine doesn't need to know crossterm exists.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AppEvent {
+    Char(char),
+    Enter,
+    Tab,
+    Backspace,
+    Delete,
+    Esc,
+    CtrlC,
+    CtrlD,
+    CtrlR,
+    CycleFilter,
+}
+
+/// Everything the event loop needs from a terminal: bring it up, read
+/// input, draw a frame, tear it down. `CrosstermBackend` drives a real
+/// terminal; the test-only `MockBackend` feeds synthetic events and keeps
+/// rendered frames for inspection without a real TTY.
+pub trait Backend {
+    fn init(&mut self) -> Result<()>;
+    fn shutdown(&mut self) -> Result<()>;
+    fn poll_event(&mut self, timeout: Duration) -> Result<Option<AppEvent>>;
+    fn draw(&mut self, draw_fn: &mut dyn FnMut(&mut Frame)) -> Result<()>;
+}
+
+pub struct CrosstermBackend {
+    terminal: ratatui::Terminal<ratatui::backend::CrosstermBackend<std::io::Stdout>>,
+}
+
+impl CrosstermBackend {
+    pub fn new() -> Result<Self> {
+        let stdout = std::io::stdout();
+        let backend = ratatui::backend::CrosstermBackend::new(stdout);
+        let terminal = ratatui::Terminal::new(backend)?;
+        Ok(CrosstermBackend { terminal })
+    }
+}
+
+impl Backend for CrosstermBackend {
+    fn init(&mut self) -> Result<()> {
+        crossterm::terminal::enable_raw_mode()?;
+        crossterm::execute!(
+            self.terminal.backend_mut(),
+            crossterm::terminal::EnterAlternateScreen
+        )?;
+        Ok(())
+    }
+
+    fn shutdown(&mut self) -> Result<()> {
+        crossterm::terminal::disable_raw_mode()?;
+        crossterm::execute!(
+            self.terminal.backend_mut(),
+            crossterm::terminal::LeaveAlternateScreen
+        )?;
+        Ok(())
+    }
+
+    fn poll_event(&mut self, timeout: Duration) -> Result<Option<AppEvent>> {
+        use crossterm::event::{self, Event, KeyCode, KeyModifiers};
+
+        if !event::poll(timeout)? {
+            return Ok(None);
+        }
+
+        let Event::Key(key) = event::read()? else {
+            return Ok(None);
+        };
+
+        let app_event = match (key.code, key.modifiers) {
+            (KeyCode::Char('c'), KeyModifiers::CONTROL) => AppEvent::CtrlC,
+            (KeyCode::Char('d'), KeyModifiers::CONTROL) => AppEvent::CtrlD,
+            (KeyCode::Char('r'), KeyModifiers::CONTROL) => AppEvent::CtrlR,
+            (KeyCode::Delete, _) => AppEvent::Delete,
+            (KeyCode::Char(c), _) => AppEvent::Char(c),
+            (KeyCode::Enter, _) => AppEvent::Enter,
+            (KeyCode::Esc, _) => AppEvent::Esc,
+            (KeyCode::Tab, _) => AppEvent::Tab,
+            (KeyCode::Backspace, _) => AppEvent::Backspace,
+            (KeyCode::F(2), _) => AppEvent::CycleFilter,
+            _ => return Ok(None),
+        };
+
+        Ok(Some(app_event))
+    }
+
+    fn draw(&mut self, draw_fn: &mut dyn FnMut(&mut Frame)) -> Result<()> {
+        self.terminal.draw(|f| draw_fn(f))?;
+        Ok(())
+    }
+}
+
+/// A headless `Backend` for tests: events are fed from a fixed queue, and
+/// frames render into an in-memory `ratatui::backend::TestBackend` buffer
+/// that can be inspected afterwards instead of a real TTY.
+#[cfg(test)]
+pub struct MockBackend {
+    events: std::collections::VecDeque<AppEvent>,
+    terminal: ratatui::Terminal<ratatui::backend::TestBackend>,
+}
+
+#[cfg(test)]
+impl MockBackend {
+    pub fn new(width: u16, height: u16, events: Vec<AppEvent>) -> Self {
+        let terminal = ratatui::Terminal::new(ratatui::backend::TestBackend::new(width, height))
+            .expect("Failed to build test terminal");
+        MockBackend {
+            events: events.into(),
+            terminal,
+        }
+    }
+
+    pub fn buffer(&self) -> &ratatui::buffer::Buffer {
+        self.terminal.backend().buffer()
+    }
+}
+
+#[cfg(test)]
+impl Backend for MockBackend {
+    fn init(&mut self) -> Result<()> {
+        Ok(())
+    }
+
+    fn shutdown(&mut self) -> Result<()> {
+        Ok(())
+    }
+
+    fn poll_event(&mut self, _timeout: Duration) -> Result<Option<AppEvent>> {
+        Ok(self.events.pop_front())
+    }
+
+    fn draw(&mut self, draw_fn: &mut dyn FnMut(&mut Frame)) -> Result<()> {
+        self.terminal.draw(|f| draw_fn(f))?;
+        Ok(())
+    }
+}
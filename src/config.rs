@@ -0,0 +1,156 @@
+use anyhow::{Context, Result};
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::fs;
+use std::path::PathBuf;
+
+/// Box titles shown around the TUI panes.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct UiConfig {
+    pub top_box_name: String,
+    pub left_box_name: String,
+    pub right_top_name: String,
+    pub right_bottom_name: String,
+}
+
+impl Default for UiConfig {
+    fn default() -> Self {
+        UiConfig {
+            top_box_name: "Word".to_string(),
+            left_box_name: "Input".to_string(),
+            right_top_name: "Explanation".to_string(),
+            right_bottom_name: "Score".to_string(),
+        }
+    }
+}
+
+/// Which Ollama models to call and how.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct ModelConfig {
+    pub generation: String,
+    pub embedding: String,
+    pub host: String,
+    pub port: u16,
+    pub temperature: f32,
+}
+
+impl Default for ModelConfig {
+    fn default() -> Self {
+        ModelConfig {
+            generation: "llama3.2:latest".to_string(),
+            embedding: "all-minilm".to_string(),
+            host: "http://localhost".to_string(),
+            port: 11434,
+            temperature: 0.2,
+        }
+    }
+}
+
+/// Named decks the user can switch between, plus which one is active.
+///
+/// Each deck's value is either `"builtin"` (the wordlist baked into the
+/// binary), a filesystem path, or an `http(s)://` URL to fetch at startup.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct WordlistConfig {
+    pub deck: String,
+    pub decks: HashMap<String, String>,
+}
+
+impl Default for WordlistConfig {
+    fn default() -> Self {
+        let mut decks = HashMap::new();
+        decks.insert("default".to_string(), "builtin".to_string());
+        WordlistConfig {
+            deck: "default".to_string(),
+            decks,
+        }
+    }
+}
+
+/// Which `StateMachine` implementation drives `generate`/`process`.
+///
+/// `script` is only read when `driver.kind = "lua"`, and points at a Lua
+/// file implementing the `generate`/`process` globals.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct DriverConfig {
+    pub kind: String,
+    pub script: String,
+}
+
+impl Default for DriverConfig {
+    fn default() -> Self {
+        DriverConfig {
+            kind: "ollama".to_string(),
+            script: String::new(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default)]
+pub struct Config {
+    pub ui: UiConfig,
+    pub model: ModelConfig,
+    pub wordlist: WordlistConfig,
+    pub driver: DriverConfig,
+}
+
+impl Config {
+    /// Load the TOML config from the user's config directory, falling back
+    /// to defaults if no file exists yet.
+    pub fn load() -> Result<Self> {
+        let path = Self::path()?;
+        if !path.exists() {
+            return Ok(Config::default());
+        }
+
+        let data = fs::read_to_string(&path)
+            .with_context(|| format!("Failed to read config file at {}", path.display()))?;
+        toml::from_str(&data)
+            .with_context(|| format!("Failed to parse config file at {}", path.display()))
+    }
+
+    fn path() -> Result<PathBuf> {
+        let dirs = directories::ProjectDirs::from("", "", "vocab-tui")
+            .context("Failed to resolve the user's config directory")?;
+        Ok(dirs.config_dir().join("config.toml"))
+    }
+
+    /// The wordlist source (`"builtin"`, a path, or a URL) for the active deck.
+    pub fn active_wordlist_source(&self) -> &str {
+        self.wordlist
+            .decks
+            .get(&self.wordlist.deck)
+            .map(String::as_str)
+            .unwrap_or("builtin")
+    }
+}
+
+/// Load a wordlist from its configured source: the builtin list, a local
+/// file, or an `http(s)://` URL.
+pub async fn load_wordlist(source: &str) -> Result<Vec<String>> {
+    if source == "builtin" {
+        return Ok(include_str!("../wordlist.txt")
+            .lines()
+            .map(str::to_string)
+            .collect());
+    }
+
+    if source.starts_with("http://") || source.starts_with("https://") {
+        let body = reqwest::get(source)
+            .await
+            .with_context(|| format!("Failed to fetch wordlist from {source}"))?
+            .text()
+            .await
+            .with_context(|| format!("Failed to read wordlist response from {source}"))?;
+        return Ok(body.lines().map(str::to_string).collect());
+    }
+
+    let data = fs::read_to_string(source)
+        .with_context(|| format!("Failed to read wordlist file at {source}"))?;
+    Ok(data.lines().map(str::to_string).collect())
+}
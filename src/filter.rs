@@ -0,0 +1,30 @@
+/// Constrains which words `StateMachine::generate` may draw from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum FilterMode {
+    #[default]
+    All,
+    Due,
+    Struggling,
+    New,
+}
+
+impl FilterMode {
+    /// Cycle to the next mode, wrapping back to `All`.
+    pub fn next(self) -> Self {
+        match self {
+            FilterMode::All => FilterMode::Due,
+            FilterMode::Due => FilterMode::Struggling,
+            FilterMode::Struggling => FilterMode::New,
+            FilterMode::New => FilterMode::All,
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            FilterMode::All => "All",
+            FilterMode::Due => "Due",
+            FilterMode::Struggling => "Struggling",
+            FilterMode::New => "New",
+        }
+    }
+}
@@ -0,0 +1,193 @@
+use anyhow::{Context, Result};
+use mlua::{Function, Lua};
+use std::sync::mpsc as std_mpsc;
+use std::thread;
+use tokio::sync::oneshot;
+
+use crate::app::StateMachine;
+use crate::config::ModelConfig;
+use crate::filter::FilterMode;
+
+/// A `generate`/`process` request for the Lua VM thread, paired with a
+/// channel to send the result back on.
+enum Command {
+    Generate {
+        filter: &'static str,
+        reply: std_mpsc::Sender<Result<String>>,
+    },
+    Process {
+        input: String,
+        logit: String,
+        reply: oneshot::Sender<Result<(f64, String)>>,
+    },
+}
+
+/// Drives `generate`/`process` from a user-provided Lua script instead of
+/// the built-in Ollama prompt and cosine-similarity scoring.
+///
+/// The script must define two globals:
+///
+/// - `generate(filter) -> string` picks the next word to drill, where
+///   `filter` is the active mode's label ("All", "Due", "Struggling", "New").
+/// - `process(word, input) -> (score, explanation)` grades an answer,
+///   where `score` is a number in `0.0..=1.0`.
+///
+/// `wordlist` (an array of strings) and `ollama_generate(model, prompt)`
+/// (an async helper that calls the configured Ollama host) are exposed as
+/// globals so a script can still lean on the LLM if it wants to.
+///
+/// `mlua::Lua` is `!Send`, and a call in progress (e.g. an `await` inside
+/// `call_async`) can't be smuggled across an `.await` point either, which is
+/// exactly what `StateMachine: Send + Sync` requires of the future returned
+/// from `process`. So the VM never leaves the thread that created it: it
+/// lives on a dedicated thread with its own single-threaded tokio runtime,
+/// and `generate`/`process` just send a `Command` over a channel and wait
+/// for the reply.
+pub struct LuaDriver {
+    commands: std_mpsc::Sender<Command>,
+}
+
+impl LuaDriver {
+    pub fn new(script_path: &str, wordlist: Vec<String>, model: &ModelConfig) -> Result<Self> {
+        let script_path = script_path.to_string();
+        let model = model.clone();
+        let (commands_tx, commands_rx) = std_mpsc::channel::<Command>();
+        let (ready_tx, ready_rx) = std_mpsc::channel::<Result<()>>();
+
+        thread::Builder::new()
+            .name("lua-driver".to_string())
+            .spawn(move || {
+                let lua = match Self::build_lua(&script_path, &wordlist, &model) {
+                    Ok(lua) => lua,
+                    Err(err) => {
+                        let _ = ready_tx.send(Err(err));
+                        return;
+                    }
+                };
+                let _ = ready_tx.send(Ok(()));
+
+                let rt = tokio::runtime::Builder::new_current_thread()
+                    .enable_time()
+                    .enable_io()
+                    .build()
+                    .expect("Failed to build Lua driver runtime");
+
+                for command in commands_rx {
+                    match command {
+                        Command::Generate { filter, reply } => {
+                            let _ = reply.send(Self::run_generate(&lua, filter));
+                        }
+                        Command::Process {
+                            input,
+                            logit,
+                            reply,
+                        } => {
+                            let _ = reply.send(rt.block_on(Self::run_process(&lua, input, logit)));
+                        }
+                    }
+                }
+            })
+            .context("Failed to spawn Lua driver thread")?;
+
+        ready_rx
+            .recv()
+            .context("Lua driver thread exited before it was ready")??;
+
+        Ok(LuaDriver {
+            commands: commands_tx,
+        })
+    }
+
+    fn build_lua(script_path: &str, wordlist: &[String], model: &ModelConfig) -> Result<Lua> {
+        let lua = Lua::new();
+
+        let words_table = lua.create_table()?;
+        for (i, word) in wordlist.iter().enumerate() {
+            words_table.set(i + 1, word.as_str())?;
+        }
+        lua.globals().set("wordlist", words_table)?;
+
+        let client = std::sync::Arc::new(ollama_rs::Ollama::new(model.host.clone(), model.port));
+        let ollama_generate =
+            lua.create_async_function(move |_, (model, prompt): (String, String)| {
+                let client = client.clone();
+                async move {
+                    let response = client
+                        .generate(
+                            ollama_rs::generation::completion::request::GenerationRequest::new(
+                                model, prompt,
+                            ),
+                        )
+                        .await
+                        .map_err(mlua::Error::external)?;
+                    Ok(response.response)
+                }
+            })?;
+        lua.globals().set("ollama_generate", ollama_generate)?;
+
+        let script = std::fs::read_to_string(script_path)
+            .with_context(|| format!("Failed to read Lua script at {script_path}"))?;
+        lua.load(&script)
+            .exec()
+            .with_context(|| format!("Failed to run Lua script at {script_path}"))?;
+
+        Ok(lua)
+    }
+
+    fn run_generate(lua: &Lua, filter: &str) -> Result<String> {
+        let generate: Function = lua
+            .globals()
+            .get("generate")
+            .context("Lua script must define a `generate` function")?;
+
+        generate
+            .call(filter)
+            .map_err(|err| anyhow::anyhow!("Lua `generate` call failed: {err}"))
+    }
+
+    async fn run_process(lua: &Lua, input: String, logit: String) -> Result<(f64, String)> {
+        let process: Function = lua
+            .globals()
+            .get("process")
+            .context("Lua script must define a `process` function")?;
+
+        let (score, explanation): (f64, String) = process
+            .call_async((logit, input))
+            .await
+            .map_err(|err| anyhow::anyhow!("Lua `process` call failed: {err}"))?;
+
+        Ok((score, explanation))
+    }
+}
+
+#[async_trait::async_trait]
+impl StateMachine for LuaDriver {
+    fn generate(&self, filter: FilterMode) -> Result<String> {
+        let (reply_tx, reply_rx) = std_mpsc::channel();
+        self.commands
+            .send(Command::Generate {
+                filter: filter.label(),
+                reply: reply_tx,
+            })
+            .map_err(|_| anyhow::anyhow!("Lua driver thread is gone"))?;
+
+        reply_rx
+            .recv()
+            .context("Lua driver thread dropped the reply channel")?
+    }
+
+    async fn process(&self, input: String, logit: String) -> Result<(f64, String)> {
+        let (reply_tx, reply_rx) = oneshot::channel();
+        self.commands
+            .send(Command::Process {
+                input,
+                logit,
+                reply: reply_tx,
+            })
+            .map_err(|_| anyhow::anyhow!("Lua driver thread is gone"))?;
+
+        reply_rx
+            .await
+            .context("Lua driver thread dropped the reply channel")?
+    }
+}
@@ -4,34 +4,66 @@ use ollama_rs::generation::embeddings::request::GenerateEmbeddingsRequest;
 use ollama_rs::generation::options::GenerationOptions;
 use ollama_rs::Ollama;
 use rand::prelude::SliceRandom;
+use std::sync::RwLock;
 
-use super::StateMachine;
+use crate::app::StateMachine;
+use crate::config::ModelConfig;
+use crate::filter::FilterMode;
+use crate::scheduler::Scheduler;
+use crate::storage::Attempt;
 
 pub struct OllamaDriver {
     client: Ollama,
-    wordlist: Vec<&'static str>,
+    wordlist: Vec<String>,
+    scheduler: RwLock<Scheduler>,
+    generation_model: String,
+    embedding_model: String,
+    temperature: f32,
 }
 
 impl OllamaDriver {
-    pub fn new(wordlist: Vec<&'static str>) -> Self {
+    /// `past_attempts` is replayed through the scheduler in timestamp
+    /// order so repetition counts, easiness factors, intervals and scores
+    /// (and with them the struggling/new filters) survive a restart instead
+    /// of starting from a blank slate. Replayed due dates are counted from
+    /// the time of the replay, not from when each attempt was made.
+    pub fn new(wordlist: Vec<String>, model: &ModelConfig, past_attempts: &[Attempt]) -> Self {
+        let mut scheduler = Scheduler::new();
+        let mut past_attempts = past_attempts.to_vec();
+        past_attempts.sort_by_key(|attempt| attempt.timestamp);
+        for attempt in &past_attempts {
+            scheduler.review(&attempt.word, attempt.score);
+        }
+
         OllamaDriver {
-            client: Ollama::default(),
+            client: Ollama::new(model.host.clone(), model.port),
             wordlist,
+            scheduler: RwLock::new(scheduler),
+            generation_model: model.generation.clone(),
+            embedding_model: model.embedding.clone(),
+            temperature: model.temperature,
         }
     }
 }
 
 #[async_trait::async_trait]
 impl StateMachine for OllamaDriver {
-    fn generate(&self) -> String {
-        let mut rng = rand::thread_rng();
-        let word = self.wordlist.choose(&mut rng).unwrap();
+    fn generate(&self, filter: FilterMode) -> Result<String> {
+        let scheduler = self.scheduler.read().expect("Failed to lock scheduler");
+        let candidates = scheduler.candidates(&self.wordlist, filter);
+
+        if let Some(word) = scheduler.earliest_due(&candidates) {
+            return Ok(word);
+        }
 
-        word.to_string()
+        let mut rng = rand::thread_rng();
+        Ok(candidates
+            .choose(&mut rng)
+            .map(|w| w.to_string())
+            .unwrap_or_default())
     }
 
     async fn process(&self, input: String, logit: String) -> Result<(f64, String)> {
-        let model = "llama3.2:latest";
         let prompt = format!(
             r#"
         For the given word: "{logit}". How well does the following sentence describe it?
@@ -44,18 +76,17 @@ impl StateMachine for OllamaDriver {
         );
 
         let stmt = self.client.generate(
-            GenerationRequest::new(model.to_string(), prompt)
-                .options(GenerationOptions::default().temperature(0.2)),
+            GenerationRequest::new(self.generation_model.clone(), prompt)
+                .options(GenerationOptions::default().temperature(self.temperature)),
         );
 
-        let model = "all-minilm";
-
         let score = self
             .client
             .generate_embeddings(GenerateEmbeddingsRequest::new(
-                model.to_string(),
+                self.embedding_model.clone(),
                 ollama_rs::generation::embeddings::request::EmbeddingsInput::Multiple(vec![
-                    input, logit,
+                    input,
+                    logit.clone(),
                 ]),
             ));
 
@@ -66,9 +97,14 @@ impl StateMachine for OllamaDriver {
         let one = score.embeddings[0].clone();
         let two = score.embeddings[1].clone();
 
-        let score = cosine_similarity(&one, &two);
+        let score = cosine_similarity(&one, &two) as f64;
+
+        self.scheduler
+            .write()
+            .expect("Failed to lock scheduler")
+            .review(&logit, score);
 
-        Ok((score as f64, help))
+        Ok((score, help))
     }
 }
 
@@ -0,0 +1,328 @@
+use std::collections::HashMap;
+use std::time::{Duration, SystemTime};
+
+use crate::filter::FilterMode;
+
+/// Initial easiness factor assigned to a word that has never been reviewed.
+const INITIAL_EF: f64 = 2.5;
+/// SM-2 never lets the easiness factor drop below this, or a word becomes
+/// effectively impossible to ever see again.
+const MIN_EF: f64 = 1.3;
+const SECS_PER_DAY: u64 = 60 * 60 * 24;
+/// Average score below which a word counts as "struggling".
+const STRUGGLING_THRESHOLD: f64 = 0.6;
+
+/// Per-word learning state for the SM-2 algorithm.
+struct WordState {
+    /// Consecutive successful repetitions.
+    n: u32,
+    /// Easiness factor: how quickly the interval grows.
+    ef: f64,
+    /// Current interval, in days.
+    interval: u32,
+    /// When this word is next due for review.
+    due: SystemTime,
+    /// Number of times this word has been reviewed.
+    attempts: u32,
+    /// Running sum of scores, for `average_score`.
+    score_sum: f64,
+}
+
+impl Default for WordState {
+    fn default() -> Self {
+        WordState {
+            n: 0,
+            ef: INITIAL_EF,
+            interval: 0,
+            due: SystemTime::now(),
+            attempts: 0,
+            score_sum: 0.0,
+        }
+    }
+}
+
+impl WordState {
+    fn average_score(&self) -> f64 {
+        if self.attempts == 0 {
+            0.0
+        } else {
+            self.score_sum / self.attempts as f64
+        }
+    }
+}
+
+/// Tracks per-word SM-2 state and decides which word is most due for review.
+pub struct Scheduler {
+    words: HashMap<String, WordState>,
+}
+
+impl Default for Scheduler {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Scheduler {
+    pub fn new() -> Self {
+        Scheduler {
+            words: HashMap::new(),
+        }
+    }
+
+    /// Record the outcome of a review and reschedule the word per SM-2.
+    ///
+    /// `score` is the cosine similarity in `0.0..=1.0` returned by
+    /// `OllamaDriver::process`; it is mapped to an SM-2 quality grade
+    /// `q` in `0..=5` via `q = round(score * 5)`.
+    pub fn review(&mut self, word: &str, score: f64) {
+        let state = self.words.entry(word.to_string()).or_default();
+        let q = (score.clamp(0.0, 1.0) * 5.0).round() as i32;
+
+        if q >= 3 {
+            state.interval = match state.n {
+                0 => 1,
+                1 => 6,
+                _ => (state.interval as f64 * state.ef).round() as u32,
+            };
+            state.n += 1;
+        } else {
+            state.n = 0;
+            state.interval = 1;
+        }
+
+        let q = q as f64;
+        state.ef = (state.ef + (0.1 - (5.0 - q) * (0.08 + (5.0 - q) * 0.02))).max(MIN_EF);
+        state.due = SystemTime::now() + Duration::from_secs(state.interval as u64 * SECS_PER_DAY);
+        state.attempts += 1;
+        state.score_sum += score;
+    }
+
+    /// Words from `wordlist` that match `filter`, falling back to the full
+    /// list when the filter would otherwise leave nothing to drill.
+    pub fn candidates<'a>(&self, wordlist: &'a [String], filter: FilterMode) -> Vec<&'a str> {
+        let all = || wordlist.iter().map(String::as_str).collect::<Vec<_>>();
+
+        let filtered: Vec<&str> = match filter {
+            FilterMode::All => return all(),
+            FilterMode::Due => {
+                let now = SystemTime::now();
+                wordlist
+                    .iter()
+                    .map(String::as_str)
+                    .filter(|w| self.words.get(*w).is_some_and(|s| s.due <= now))
+                    .collect()
+            }
+            FilterMode::Struggling => wordlist
+                .iter()
+                .map(String::as_str)
+                .filter(|w| {
+                    self.words
+                        .get(*w)
+                        .is_some_and(|s| s.attempts > 0 && s.average_score() < STRUGGLING_THRESHOLD)
+                })
+                .collect(),
+            FilterMode::New => wordlist
+                .iter()
+                .map(String::as_str)
+                .filter(|w| !self.words.contains_key(*w))
+                .collect(),
+        };
+
+        if filtered.is_empty() {
+            all()
+        } else {
+            filtered
+        }
+    }
+
+    /// Among `candidates`, the overdue word with the earliest due date.
+    pub fn earliest_due(&self, candidates: &[&str]) -> Option<String> {
+        let now = SystemTime::now();
+        candidates
+            .iter()
+            .filter_map(|&w| self.words.get(w).map(|s| (w, s)))
+            .filter(|(_, s)| s.due <= now)
+            .min_by_key(|(_, s)| s.due)
+            .map(|(w, _)| w.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn first_correct_review_sets_interval_to_one() {
+        let mut scheduler = Scheduler::new();
+        scheduler.review("apple", 1.0);
+
+        let state = &scheduler.words["apple"];
+        assert_eq!(state.n, 1);
+        assert_eq!(state.interval, 1);
+    }
+
+    #[test]
+    fn second_correct_review_sets_interval_to_six() {
+        let mut scheduler = Scheduler::new();
+        scheduler.review("apple", 1.0);
+        scheduler.review("apple", 1.0);
+
+        let state = &scheduler.words["apple"];
+        assert_eq!(state.n, 2);
+        assert_eq!(state.interval, 6);
+    }
+
+    #[test]
+    fn later_correct_reviews_grow_interval_by_ef() {
+        let mut scheduler = Scheduler::new();
+        for _ in 0..2 {
+            scheduler.review("apple", 1.0);
+        }
+        // The interval grows by the EF in effect before this review updates it.
+        let ef_before = scheduler.words["apple"].ef;
+        scheduler.review("apple", 1.0);
+
+        let state = &scheduler.words["apple"];
+        assert_eq!(state.n, 3);
+        assert_eq!(state.interval, (6.0 * ef_before).round() as u32);
+    }
+
+    #[test]
+    fn failing_review_resets_repetition_count_and_interval() {
+        let mut scheduler = Scheduler::new();
+        scheduler.review("apple", 1.0);
+        scheduler.review("apple", 1.0);
+        scheduler.review("apple", 0.0);
+
+        let state = &scheduler.words["apple"];
+        assert_eq!(state.n, 0);
+        assert_eq!(state.interval, 1);
+    }
+
+    #[test]
+    fn easiness_factor_never_drops_below_the_floor() {
+        let mut scheduler = Scheduler::new();
+        for _ in 0..20 {
+            scheduler.review("apple", 0.0);
+        }
+
+        assert_eq!(scheduler.words["apple"].ef, MIN_EF);
+    }
+
+    #[test]
+    fn review_tracks_attempts_and_average_score() {
+        let mut scheduler = Scheduler::new();
+        scheduler.review("apple", 1.0);
+        scheduler.review("apple", 0.0);
+
+        let state = &scheduler.words["apple"];
+        assert_eq!(state.attempts, 2);
+        assert_eq!(state.average_score(), 0.5);
+    }
+
+    fn wordlist(words: &[&str]) -> Vec<String> {
+        words.iter().map(|w| w.to_string()).collect()
+    }
+
+    #[test]
+    fn candidates_all_returns_the_whole_wordlist() {
+        let mut scheduler = Scheduler::new();
+        scheduler.review("apple", 1.0);
+        let words = wordlist(&["apple", "banana"]);
+
+        let mut candidates = scheduler.candidates(&words, FilterMode::All);
+        candidates.sort();
+        assert_eq!(candidates, vec!["apple", "banana"]);
+    }
+
+    #[test]
+    fn candidates_new_excludes_reviewed_words() {
+        let mut scheduler = Scheduler::new();
+        scheduler.review("apple", 1.0);
+        let words = wordlist(&["apple", "banana"]);
+
+        assert_eq!(
+            scheduler.candidates(&words, FilterMode::New),
+            vec!["banana"]
+        );
+    }
+
+    #[test]
+    fn candidates_struggling_returns_only_low_average_words() {
+        let mut scheduler = Scheduler::new();
+        scheduler.review("apple", 0.0);
+        scheduler.review("banana", 1.0);
+        let words = wordlist(&["apple", "banana"]);
+
+        assert_eq!(
+            scheduler.candidates(&words, FilterMode::Struggling),
+            vec!["apple"]
+        );
+    }
+
+    #[test]
+    fn candidates_due_returns_only_overdue_words() {
+        let mut scheduler = Scheduler::new();
+        scheduler.words.insert(
+            "apple".to_string(),
+            WordState {
+                due: SystemTime::now() - Duration::from_secs(SECS_PER_DAY),
+                ..WordState::default()
+            },
+        );
+        scheduler.words.insert(
+            "banana".to_string(),
+            WordState {
+                due: SystemTime::now() + Duration::from_secs(SECS_PER_DAY),
+                ..WordState::default()
+            },
+        );
+        let words = wordlist(&["apple", "banana"]);
+
+        assert_eq!(scheduler.candidates(&words, FilterMode::Due), vec!["apple"]);
+    }
+
+    #[test]
+    fn candidates_falls_back_to_all_when_filter_matches_nothing() {
+        let mut scheduler = Scheduler::new();
+        scheduler.review("apple", 1.0);
+        scheduler.review("banana", 1.0);
+        let words = wordlist(&["apple", "banana"]);
+
+        let mut candidates = scheduler.candidates(&words, FilterMode::New);
+        candidates.sort();
+        assert_eq!(candidates, vec!["apple", "banana"]);
+    }
+
+    #[test]
+    fn earliest_due_picks_the_most_overdue_candidate() {
+        let mut scheduler = Scheduler::new();
+        scheduler.words.insert(
+            "apple".to_string(),
+            WordState {
+                due: SystemTime::now() - Duration::from_secs(SECS_PER_DAY),
+                ..WordState::default()
+            },
+        );
+        scheduler.words.insert(
+            "banana".to_string(),
+            WordState {
+                due: SystemTime::now() - Duration::from_secs(2 * SECS_PER_DAY),
+                ..WordState::default()
+            },
+        );
+
+        assert_eq!(
+            scheduler.earliest_due(&["apple", "banana"]),
+            Some("banana".to_string())
+        );
+    }
+
+    #[test]
+    fn earliest_due_returns_none_when_nothing_is_overdue() {
+        let mut scheduler = Scheduler::new();
+        scheduler.review("apple", 1.0);
+
+        assert_eq!(scheduler.earliest_due(&["apple"]), None);
+    }
+}
@@ -0,0 +1,93 @@
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::PathBuf;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// A single graded attempt at a word, kept for lifetime stats and as the
+/// record of what the user has actually seen.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Attempt {
+    pub word: String,
+    pub input: String,
+    pub score: f64,
+    pub explanation: String,
+    pub timestamp: u64,
+}
+
+/// The full review history, persisted as a single JSON file under the
+/// user's data directory.
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct History {
+    attempts: Vec<Attempt>,
+}
+
+impl History {
+    /// Load history from disk, or start fresh if none exists yet.
+    pub fn load() -> Result<Self> {
+        let path = Self::path()?;
+        if !path.exists() {
+            return Ok(History::default());
+        }
+
+        let data = fs::read_to_string(&path)
+            .with_context(|| format!("Failed to read history file at {}", path.display()))?;
+        serde_json::from_str(&data)
+            .with_context(|| format!("Failed to parse history file at {}", path.display()))
+    }
+
+    pub fn save(&self) -> Result<()> {
+        let path = Self::path()?;
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)
+                .with_context(|| format!("Failed to create data dir at {}", parent.display()))?;
+        }
+
+        let data = serde_json::to_string_pretty(self)?;
+        fs::write(&path, data)
+            .with_context(|| format!("Failed to write history file at {}", path.display()))
+    }
+
+    pub fn record(&mut self, word: String, input: String, score: f64, explanation: String) {
+        let timestamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_secs())
+            .unwrap_or(0);
+
+        self.attempts.push(Attempt {
+            word,
+            input,
+            score,
+            explanation,
+            timestamp,
+        });
+    }
+
+    /// All recorded attempts, oldest first, for replaying scheduler state
+    /// or other startup bookkeeping.
+    pub fn attempts(&self) -> &[Attempt] {
+        &self.attempts
+    }
+
+    pub fn words_seen(&self) -> usize {
+        self.attempts
+            .iter()
+            .map(|a| a.word.as_str())
+            .collect::<std::collections::HashSet<_>>()
+            .len()
+    }
+
+    pub fn average_score(&self) -> f64 {
+        if self.attempts.is_empty() {
+            return 0.0;
+        }
+
+        self.attempts.iter().map(|a| a.score).sum::<f64>() / self.attempts.len() as f64
+    }
+
+    fn path() -> Result<PathBuf> {
+        let dirs = directories::ProjectDirs::from("", "", "vocab-tui")
+            .context("Failed to resolve the user's data directory")?;
+        Ok(dirs.data_dir().join("history.json"))
+    }
+}